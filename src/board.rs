@@ -0,0 +1,287 @@
+use crate::Rules;
+use macroquad::prelude::IVec2;
+use std::collections::{HashMap, HashSet};
+
+/// Which storage strategy a `Board` uses. Dense suits small-to-medium boards
+/// with a high fraction of live cells; sparse keeps tick cost proportional
+/// to the live population, which matters once boards grow into the hundreds
+/// of cells per side.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardBackend {
+    Dense,
+    Sparse,
+}
+
+#[derive(Clone)]
+pub enum Board {
+    Dense(DenseBoard),
+    Sparse(SparseBoard),
+}
+
+#[derive(Clone)]
+pub struct DenseBoard {
+    pub cx: i32,
+    pub cy: i32,
+    pub states: Vec<i8>,
+}
+
+#[derive(Clone)]
+pub struct SparseBoard {
+    pub cx: i32,
+    pub cy: i32,
+    pub live: HashSet<(i32, i32)>,
+    pub lifetimes: HashMap<(i32, i32), i8>,
+}
+
+fn wrap(v: i32, m: i32) -> i32 {
+    (v + m) % m
+}
+
+impl DenseBoard {
+    fn new(dimensions: IVec2) -> DenseBoard {
+        DenseBoard {
+            cx: dimensions.x,
+            cy: dimensions.y,
+            states: vec![0; (dimensions.x * dimensions.y) as usize],
+        }
+    }
+
+    fn get_cell_at_position(&self, x: i32, y: i32) -> i8 {
+        let nx = wrap(x, self.cx);
+        let ny = wrap(y, self.cy);
+        self.states[(ny * self.cx + nx) as usize]
+    }
+
+    fn set_cell_at_position(&mut self, x: i32, y: i32, state: i8) {
+        let index = (y * self.cx + x) as usize;
+        self.states[index] = state;
+    }
+
+    fn tick(&self, rules: &Rules) -> DenseBoard {
+        let mut new_game_state = DenseBoard::new(IVec2::new(self.cx, self.cy));
+
+        for y in 0..self.cy {
+            for x in 0..self.cx {
+                // Only fully-vital (max_state) neighbors count; dying,
+                // fading-out cells are invisible to the rule tables.
+                let mut neighbors = 0u8;
+
+                for i in 0..3 {
+                    for j in 0..3 {
+                        if i == 1 && j == 1 {
+                            continue;
+                        }
+                        let px = x + i - 1;
+                        let py = y + j - 1;
+                        if self.get_cell_at_position(px, py) == rules.max_state {
+                            neighbors += 1;
+                        }
+                    }
+                }
+
+                let current = self.get_cell_at_position(x, y);
+                let next_state = if current == rules.max_state {
+                    if rules.survive[neighbors as usize] {
+                        rules.max_state
+                    } else {
+                        current - 1
+                    }
+                } else if current > 0 {
+                    current - 1
+                } else if rules.birth[neighbors as usize] {
+                    rules.max_state
+                } else {
+                    0
+                };
+
+                new_game_state.set_cell_at_position(x, y, next_state);
+            }
+        }
+
+        new_game_state
+    }
+}
+
+impl SparseBoard {
+    fn new(dimensions: IVec2) -> SparseBoard {
+        SparseBoard {
+            cx: dimensions.x,
+            cy: dimensions.y,
+            live: HashSet::new(),
+            lifetimes: HashMap::new(),
+        }
+    }
+
+    fn get_cell_at_position(&self, x: i32, y: i32) -> i8 {
+        let key = (wrap(x, self.cx), wrap(y, self.cy));
+        *self.lifetimes.get(&key).unwrap_or(&0)
+    }
+
+    fn set_cell_at_position(&mut self, x: i32, y: i32, state: i8) {
+        let key = (x, y);
+        if state > 0 {
+            self.live.insert(key);
+            self.lifetimes.insert(key, state);
+        } else {
+            self.live.remove(&key);
+            self.lifetimes.remove(&key);
+        }
+    }
+
+    fn tick(&self, rules: &Rules) -> SparseBoard {
+        // Only fully-vital (max_state) cells count as live neighbors.
+        let mut neighbor_counts: HashMap<(i32, i32), u8> = HashMap::new();
+        for &(x, y) in &self.live {
+            if self.lifetimes.get(&(x, y)) != Some(&rules.max_state) {
+                continue;
+            }
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if dx == 0 && dy == 0 {
+                        continue;
+                    }
+                    let key = (wrap(x + dx, self.cx), wrap(y + dy, self.cy));
+                    *neighbor_counts.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut new_board = SparseBoard::new(IVec2::new(self.cx, self.cy));
+
+        // Every cell currently holding any charge (full or fading) ages
+        // forward; dying cells can neither survive nor count as neighbors.
+        for (&pos, &state) in &self.lifetimes {
+            let neighbors = neighbor_counts.get(&pos).copied().unwrap_or(0);
+            let next_state = if state == rules.max_state && rules.survive[neighbors as usize] {
+                rules.max_state
+            } else {
+                state - 1
+            };
+            if next_state > 0 {
+                new_board.lifetimes.insert(pos, next_state);
+                if next_state == rules.max_state {
+                    new_board.live.insert(pos);
+                }
+            }
+        }
+
+        for (&pos, &neighbors) in &neighbor_counts {
+            if !self.lifetimes.contains_key(&pos) && rules.birth[neighbors as usize] {
+                new_board.lifetimes.insert(pos, rules.max_state);
+                new_board.live.insert(pos);
+            }
+        }
+
+        new_board
+    }
+}
+
+impl Board {
+    pub fn new(dimensions: IVec2, backend: BoardBackend) -> Board {
+        match backend {
+            BoardBackend::Dense => Board::Dense(DenseBoard::new(dimensions)),
+            BoardBackend::Sparse => Board::Sparse(SparseBoard::new(dimensions)),
+        }
+    }
+
+    pub fn backend(&self) -> BoardBackend {
+        match self {
+            Board::Dense(_) => BoardBackend::Dense,
+            Board::Sparse(_) => BoardBackend::Sparse,
+        }
+    }
+
+    /// Rebuilds the board with a different backend, preserving live cells.
+    /// `max_state` is needed to place transferred cells correctly on a
+    /// sparse target: only cells at `max_state` belong in `live`, fading
+    /// intermediate-state cells go in `lifetimes` only.
+    pub fn with_backend(&self, backend: BoardBackend, max_state: i8) -> Board {
+        if self.backend() == backend {
+            return self.clone();
+        }
+        let mut new_board = Board::new(IVec2::new(self.cx(), self.cy()), backend);
+        for (x, y, state) in self.live_cells() {
+            match &mut new_board {
+                Board::Dense(b) => b.set_cell_at_position(x, y, state),
+                Board::Sparse(b) => {
+                    let key = (x, y);
+                    b.lifetimes.insert(key, state);
+                    if state == max_state {
+                        b.live.insert(key);
+                    }
+                }
+            }
+        }
+        new_board
+    }
+
+    pub fn cx(&self) -> i32 {
+        match self {
+            Board::Dense(b) => b.cx,
+            Board::Sparse(b) => b.cx,
+        }
+    }
+
+    pub fn cy(&self) -> i32 {
+        match self {
+            Board::Dense(b) => b.cy,
+            Board::Sparse(b) => b.cy,
+        }
+    }
+
+    pub fn get_cell_at_position(&self, x: i32, y: i32) -> i8 {
+        match self {
+            Board::Dense(b) => b.get_cell_at_position(x, y),
+            Board::Sparse(b) => b.get_cell_at_position(x, y),
+        }
+    }
+
+    pub fn set_cell_at_position(&mut self, x: i32, y: i32, state: i8) {
+        match self {
+            Board::Dense(b) => b.set_cell_at_position(x, y, state),
+            Board::Sparse(b) => b.set_cell_at_position(x, y, state),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        match self {
+            Board::Dense(b) => b.states = vec![0; (b.cx * b.cy) as usize],
+            Board::Sparse(b) => {
+                b.live.clear();
+                b.lifetimes.clear();
+            }
+        }
+    }
+
+    pub fn tick(&self, rules: &Rules) -> Board {
+        match self {
+            Board::Dense(b) => Board::Dense(b.tick(rules)),
+            Board::Sparse(b) => Board::Sparse(b.tick(rules)),
+        }
+    }
+
+    /// Every non-dead cell as `(x, y, state)`. For a dense board this scans
+    /// the whole grid; for a sparse board it reads the `lifetimes` map, so
+    /// fading cells are included as well as those in `live`.
+    pub fn live_cells(&self) -> Vec<(i32, i32, i8)> {
+        match self {
+            Board::Dense(b) => {
+                let mut cells = Vec::new();
+                for y in 0..b.cy {
+                    for x in 0..b.cx {
+                        let state = b.get_cell_at_position(x, y);
+                        if state > 0 {
+                            cells.push((x, y, state));
+                        }
+                    }
+                }
+                cells
+            }
+            Board::Sparse(b) => b
+                .lifetimes
+                .iter()
+                .map(|(&(x, y), &state)| (x, y, state))
+                .collect(),
+        }
+    }
+}
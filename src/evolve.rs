@@ -0,0 +1,163 @@
+use crate::rules;
+use crate::{Board, Rules};
+use macroquad::rand;
+
+/// One candidate rule: the 18 booleans of `birth`/`survive`, i.e. a point in
+/// the same rule space `rules::parse_rule_string` reads and writes.
+#[derive(Clone)]
+pub struct Genome {
+    pub birth: [bool; 9],
+    pub survive: [bool; 9],
+}
+
+impl Genome {
+    pub fn random() -> Genome {
+        let mut bits = [false; 18];
+        for bit in bits.iter_mut() {
+            *bit = rand::gen_range(0, 2) == 1;
+        }
+        Genome::from_bits(bits)
+    }
+
+    pub fn to_rules(&self, max_state: i8) -> Rules {
+        Rules {
+            birth: self.birth.to_vec(),
+            survive: self.survive.to_vec(),
+            max_state,
+        }
+    }
+
+    pub fn rule_string(&self) -> String {
+        rules::format_rule_string(&self.birth, &self.survive)
+    }
+
+    fn bits(&self) -> [bool; 18] {
+        let mut bits = [false; 18];
+        bits[..9].copy_from_slice(&self.birth);
+        bits[9..].copy_from_slice(&self.survive);
+        bits
+    }
+
+    fn from_bits(bits: [bool; 18]) -> Genome {
+        let mut birth = [false; 9];
+        let mut survive = [false; 9];
+        birth.copy_from_slice(&bits[..9]);
+        survive.copy_from_slice(&bits[9..]);
+        Genome { birth, survive }
+    }
+
+    fn crossover(&self, other: &Genome) -> Genome {
+        let a = self.bits();
+        let b = other.bits();
+        let point = rand::gen_range(0, a.len());
+        let mut child = [false; 18];
+        for (i, bit) in child.iter_mut().enumerate() {
+            *bit = if i < point { a[i] } else { b[i] };
+        }
+        Genome::from_bits(child)
+    }
+
+    fn mutate(&mut self, rate: f32) {
+        let mut bits = self.bits();
+        for bit in bits.iter_mut() {
+            if rand::gen_range(0.0, 1.0) < rate {
+                *bit = !*bit;
+            }
+        }
+        *self = Genome::from_bits(bits);
+    }
+}
+
+/// What a generation is scored against: how close the average live-cell
+/// density should stay to `target_density` over `ticks` steps.
+#[derive(Clone, Copy)]
+pub struct EvolveGoal {
+    pub target_density: f32,
+    pub ticks: u32,
+}
+
+/// Runs a genome for `goal.ticks` steps on a clone of `seed_board` and
+/// scores it by how close the average density stayed to the target,
+/// penalizing extinction or total saturation.
+fn evaluate(genome: &Genome, seed_board: &Board, goal: &EvolveGoal) -> f32 {
+    let rules = genome.to_rules(1);
+    let mut board = seed_board.clone();
+    let total_cells = (board.cx() * board.cy()) as f32;
+    let mut density_sum = 0.0;
+    let mut penalty = 0.0;
+
+    for _ in 0..goal.ticks {
+        board = board.tick(&rules);
+        let density = board.live_cells().len() as f32 / total_cells;
+        density_sum += density;
+
+        if density == 0.0 {
+            penalty = 1.0;
+            break;
+        }
+        if density > 0.98 {
+            penalty = 1.0;
+            break;
+        }
+    }
+
+    let avg_density = density_sum / goal.ticks as f32;
+    -(avg_density - goal.target_density).abs() - penalty
+}
+
+/// A generation of candidate rules, evolved by keeping the fittest and
+/// breeding the rest from them.
+#[derive(Clone)]
+pub struct Population {
+    genomes: Vec<Genome>,
+}
+
+impl Population {
+    pub fn random(size: usize) -> Population {
+        Population {
+            genomes: (0..size).map(|_| Genome::random()).collect(),
+        }
+    }
+
+    /// Scores every genome against `goal` on a clone of `seed_board`, then
+    /// breeds the next generation from the top fifth via single-point
+    /// crossover with per-bit mutation. Returns the next population along
+    /// with this generation's best genome and its fitness.
+    pub fn evolve(
+        &self,
+        seed_board: &Board,
+        goal: &EvolveGoal,
+        mutation_rate: f32,
+    ) -> (Population, Genome, f32) {
+        let mut scored: Vec<(Genome, f32)> = self
+            .genomes
+            .iter()
+            .map(|genome| (genome.clone(), evaluate(genome, seed_board, goal)))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let elite_count = (scored.len() / 5).max(2);
+        let elites: Vec<Genome> = scored[..elite_count]
+            .iter()
+            .map(|(genome, _)| genome.clone())
+            .collect();
+
+        let mut next_generation = elites.clone();
+        while next_generation.len() < self.genomes.len() {
+            let a = &elites[rand::gen_range(0, elites.len())];
+            let b = &elites[rand::gen_range(0, elites.len())];
+            let mut child = a.crossover(b);
+            child.mutate(mutation_rate);
+            next_generation.push(child);
+        }
+
+        let (best_genome, best_fitness) = scored[0].clone();
+        (
+            Population {
+                genomes: next_generation,
+            },
+            best_genome,
+            best_fitness,
+        )
+    }
+}
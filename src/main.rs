@@ -1,9 +1,30 @@
 use egui::Pos2;
 use macroquad::prelude::*;
 use std::mem;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::{thread, time};
 
+mod board;
+mod evolve;
+mod patterns;
+mod rules;
+
+use board::{Board, BoardBackend};
+use evolve::{EvolveGoal, Population};
+use rules::RulePreset;
+
+/// Size of a single cell in world units, independent of zoom. Zooming
+/// changes how many world units fit on screen, not this value, so cells
+/// stay crisp instead of being stretched to fill the window.
+const CELL_SIZE: f32 = 1.0;
+
+/// How many ticks the queue is allowed to fall behind before we start
+/// dropping the backlog instead of trying to catch up all at once.
+const MAX_QUEUED_TICKS: u32 = 8;
+
+type TickOutcome = Result<Option<time::Duration>, String>;
+
 #[derive(Clone)]
 struct SimulationConfig {
     pub board_size: IVec2,
@@ -17,6 +38,22 @@ struct SimulationConfig {
     pub error: bool,
     pub alive_color: Color,
     pub dead_color: Color,
+    pub pattern_path: String,
+    pub status_message: String,
+    pub board_backend: BoardBackend,
+    pub translation: Vec2,
+    pub zoom: f32,
+    pub rule_input: String,
+    pub selected_preset: Option<RulePreset>,
+    pub queued_ticks: u32,
+    pub last_tick_duration: f32,
+    pub evolve_mode: bool,
+    pub evolve_target_density: f32,
+    pub evolve_ticks: u32,
+    pub evolve_mutation_rate: f32,
+    pub evolve_generation: u32,
+    pub evolve_best_rule: String,
+    pub evolve_best_fitness: f32,
 }
 
 impl SimulationConfig {
@@ -33,59 +70,61 @@ impl SimulationConfig {
             error: false,
             alive_color: BLACK,
             dead_color: WHITE,
+            pattern_path: String::new(),
+            status_message: String::new(),
+            board_backend: BoardBackend::Dense,
+            translation: Vec2::new(
+                board_size.x as f32 * CELL_SIZE / 2.0,
+                board_size.y as f32 * CELL_SIZE / 2.0,
+            ),
+            zoom: 20.0,
+            rule_input: RulePreset::ConwaysLife.rule_string().to_string(),
+            selected_preset: Some(RulePreset::ConwaysLife),
+            queued_ticks: 0,
+            last_tick_duration: 0.0,
+            evolve_mode: false,
+            evolve_target_density: 0.2,
+            evolve_ticks: 50,
+            evolve_mutation_rate: 0.05,
+            evolve_generation: 0,
+            evolve_best_rule: String::new(),
+            evolve_best_fitness: f32::NEG_INFINITY,
         }
     }
 }
 
-#[derive(Clone)]
-struct Board {
-    pub cx: i32,
-    pub cy: i32,
-    pub states: Vec<i8>,
+/// Builds the board's viewport camera from the current pan/zoom state.
+/// `zoom` is interpreted as pixels-per-world-unit at the current window size.
+fn make_camera(simulation_cfg: &SimulationConfig) -> Camera2D {
+    Camera2D {
+        target: simulation_cfg.translation,
+        zoom: vec2(
+            2.0 * simulation_cfg.zoom / screen_width(),
+            2.0 * simulation_cfg.zoom / screen_height(),
+        ),
+        ..Default::default()
+    }
 }
 
 #[derive(Clone)]
 struct Rules {
     pub birth: Vec<bool>,
     pub survive: Vec<bool>,
-    pub adding_lifetime: i8,
-}
-
-impl Board {
-    pub fn new(dimensions: IVec2) -> Board {
-        Board {
-            cx: dimensions.x,
-            cy: dimensions.y,
-            states: vec![0; (dimensions.x * dimensions.y) as usize],
-        }
-    }
-
-    pub fn get_cell_at_position(&self, x: i32, y: i32) -> i8 {
-        let nx = (x + self.cx) % self.cx;
-        let ny = (y + self.cy) % self.cy;
-
-        let index = (ny * self.cx + nx) as usize;
-        self.states[index]
-    }
-
-    pub fn set_cell_at_position(&mut self, x: i32, y: i32, state: i8) {
-        let index = (y * self.cx + x) as usize;
-        self.states[index] = state;
-    }
-
-    pub fn lower_cell_lifetime(&mut self, x: i32, y: i32, amount: i8) {
-        let index = (y * self.cx + x) as usize;
-        self.states[index] -= amount;
-        self.states[index] = self.states[index].max(0);
-    }
+    /// Full vitality a newly-born or surviving cell is set to. Cells that
+    /// fail to survive fade toward 0 by one state per tick instead of dying
+    /// outright, producing Generations-style aging trails.
+    pub max_state: i8,
 }
 
 #[macroquad::main("Cellular Automata")]
 async fn main() {
-    let game_mtx = Arc::new(Mutex::new(Board::new(const_ivec2!([26, 24]))));
+    let game_mtx = Arc::new(Mutex::new(Board::new(
+        const_ivec2!([26, 24]),
+        BoardBackend::Dense,
+    )));
 
     //Initial board setup
-    let mut c_game = game_mtx.lock().unwrap();
+    let mut c_game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
 
     c_game.set_cell_at_position(1, 1, 1);
     c_game.set_cell_at_position(2, 1, 1);
@@ -93,17 +132,23 @@ async fn main() {
 
     drop(c_game);
 
-    let rules = Rules {
+    let mut rules = Rules {
         birth: vec![false, false, false, true, false, false, false, false, false],
         survive: vec![false, false, true, true, false, false, false, false, false],
-        adding_lifetime: 1,
+        max_state: 1,
     };
 
     let mut cell_below_mouse = IVec2::new(0, 0);
 
     let mut simulation_cfg = SimulationConfig::new(const_ivec2!([26, 20]));
 
-    let mut handler = thread::spawn(|| {});
+    let mut handler = thread::spawn(|| -> TickOutcome { Ok(None) });
+    let mut last_tick_at = time::Instant::now();
+
+    let mut evolve_population = Population::random(30);
+    let mut evolve_handler: Option<thread::JoinHandle<(Population, evolve::Genome, f32)>> = None;
+
+    let mut prev_mouse = Vec2::new(mouse_position().0, mouse_position().1);
 
     loop {
         egui_macroquad::ui(|egui_ctx| {
@@ -120,21 +165,149 @@ async fn main() {
                         egui::Slider::new(&mut simulation_cfg.wait_time, 0f32..=100f32)
                             .text("Tick time"),
                     );
+                    ui.label(format!(
+                        "Last tick: {:.1} ms ({:.1} ticks/s)",
+                        simulation_cfg.last_tick_duration * 1000.0,
+                        if simulation_cfg.last_tick_duration > 0.0 {
+                            1.0 / simulation_cfg.last_tick_duration
+                        } else {
+                            0.0
+                        },
+                    ));
+                    ui.label(format!("Queued ticks: {}", simulation_cfg.queued_ticks));
                     ui.label("Cell controls");
                     ui.checkbox(&mut simulation_cfg.drawing, "Draw Cells");
                     ui.checkbox(&mut simulation_cfg.erasing, "Erase Cells");
                     if ui.button("Clear board").clicked() {
-                        let mut c_game = game_mtx.lock().unwrap();
-                        c_game.states = vec![0; (c_game.cx * c_game.cy) as usize];
+                        let mut c_game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
+                        c_game.clear();
                         drop(c_game);
                     }
                     if ui.button("Randomize Field").clicked() {
-                        let mut c_game = game_mtx.lock().unwrap();
-                        for i in 0..(c_game.cx * c_game.cy) as usize {
-                            c_game.states[i] = if rand::rand() % 2 == 0 { 1 } else { 0 };
+                        let mut c_game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
+                        for y in 0..c_game.cy() {
+                            for x in 0..c_game.cx() {
+                                let state = if rand::rand() % 2 == 0 {
+                                    rules.max_state
+                                } else {
+                                    0
+                                };
+                                c_game.set_cell_at_position(x, y, state);
+                            }
                         }
                         drop(c_game);
                     }
+                    ui.horizontal(|ui| {
+                        ui.label("Board backend:");
+                        if ui
+                            .radio(simulation_cfg.board_backend == BoardBackend::Dense, "Dense")
+                            .clicked()
+                        {
+                            simulation_cfg.board_backend = BoardBackend::Dense;
+                        }
+                        if ui
+                            .radio(
+                                simulation_cfg.board_backend == BoardBackend::Sparse,
+                                "Sparse",
+                            )
+                            .clicked()
+                        {
+                            simulation_cfg.board_backend = BoardBackend::Sparse;
+                        }
+                    });
+                    ui.label("Rules");
+                    ui.add(
+                        egui::Slider::new(&mut rules.max_state, 1..=24).text("Max state"),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label("B/S:");
+                        ui.text_edit_singleline(&mut simulation_cfg.rule_input);
+                        if ui.button("Apply").clicked() {
+                            match rules::parse_rule_string(&simulation_cfg.rule_input) {
+                                Ok((birth, survive)) => {
+                                    rules.birth = birth;
+                                    rules.survive = survive;
+                                    simulation_cfg.selected_preset = None;
+                                }
+                                Err(e) => {
+                                    simulation_cfg.status_message = e.to_string();
+                                    simulation_cfg.error = true;
+                                }
+                            }
+                        }
+                    });
+                    egui::ComboBox::from_label("Preset")
+                        .selected_text(
+                            simulation_cfg
+                                .selected_preset
+                                .map(|p| p.name())
+                                .unwrap_or("Custom"),
+                        )
+                        .show_ui(ui, |ui| {
+                            for preset in RulePreset::ALL {
+                                let selected = simulation_cfg.selected_preset == Some(*preset);
+                                if ui.selectable_label(selected, preset.name()).clicked() {
+                                    simulation_cfg.selected_preset = Some(*preset);
+                                    simulation_cfg.rule_input = preset.rule_string().to_string();
+                                    if let Ok((birth, survive)) =
+                                        rules::parse_rule_string(preset.rule_string())
+                                    {
+                                        rules.birth = birth;
+                                        rules.survive = survive;
+                                    }
+                                }
+                            }
+                        });
+                    ui.label("Evolve Rules");
+                    ui.checkbox(&mut simulation_cfg.evolve_mode, "Evolve Rules mode");
+                    if simulation_cfg.evolve_mode {
+                        ui.add(
+                            egui::Slider::new(&mut simulation_cfg.evolve_target_density, 0.0..=1.0)
+                                .text("Target density"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut simulation_cfg.evolve_ticks, 10..=200)
+                                .text("Ticks per genome"),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut simulation_cfg.evolve_mutation_rate, 0.0..=0.2)
+                                .text("Mutation rate"),
+                        );
+
+                        let running = evolve_handler.is_some();
+                        ui.add_enabled_ui(!running, |ui| {
+                            if ui.button("Run generation").clicked() {
+                                let seed_board = game_mtx.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                                let goal = EvolveGoal {
+                                    target_density: simulation_cfg.evolve_target_density,
+                                    ticks: simulation_cfg.evolve_ticks,
+                                };
+                                let mutation_rate = simulation_cfg.evolve_mutation_rate;
+                                let population = evolve_population.clone();
+                                evolve_handler = Some(thread::spawn(move || {
+                                    population.evolve(&seed_board, &goal, mutation_rate)
+                                }));
+                            }
+                        });
+                        if running {
+                            ui.label("Evaluating generation...");
+                        }
+                        ui.label(format!("Generation: {}", simulation_cfg.evolve_generation));
+                        ui.label(format!(
+                            "Best so far: {} (fitness {:.3})",
+                            simulation_cfg.evolve_best_rule, simulation_cfg.evolve_best_fitness
+                        ));
+                        if ui.button("Adopt best rule").clicked() {
+                            if let Ok((birth, survive)) =
+                                rules::parse_rule_string(&simulation_cfg.evolve_best_rule)
+                            {
+                                rules.birth = birth;
+                                rules.survive = survive;
+                                simulation_cfg.selected_preset = None;
+                                simulation_cfg.rule_input = simulation_cfg.evolve_best_rule.clone();
+                            }
+                        }
+                    }
                     ui.label("Board Controls");
                     ui.add(
                         egui::Slider::new(&mut simulation_cfg.board_size.x, 1..=500)
@@ -145,6 +318,47 @@ async fn main() {
                             .text("Board Height"),
                     );
                     ui.checkbox(&mut simulation_cfg.draw_borders, "Draw borders");
+                    if ui.button("Reset view").clicked() {
+                        simulation_cfg.translation = Vec2::new(
+                            simulation_cfg.board_size.x as f32 * CELL_SIZE / 2.0,
+                            simulation_cfg.board_size.y as f32 * CELL_SIZE / 2.0,
+                        );
+                        simulation_cfg.zoom = 20.0;
+                    }
+                    ui.label("Patterns");
+                    ui.text_edit_singleline(&mut simulation_cfg.pattern_path);
+                    ui.horizontal(|ui| {
+                        if ui.button("Load").clicked() {
+                            let mut c_game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
+                            match patterns::load_pattern_file(Path::new(&simulation_cfg.pattern_path))
+                            {
+                                Ok(pattern) => {
+                                    patterns::stamp_pattern(&mut c_game, &pattern, rules.max_state);
+                                    simulation_cfg.board_size =
+                                        IVec2::new(c_game.cx(), c_game.cy());
+                                    simulation_cfg.status_message = "Pattern loaded".to_string();
+                                }
+                                Err(e) => {
+                                    simulation_cfg.status_message = e.to_string();
+                                }
+                            }
+                            drop(c_game);
+                        }
+                        if ui.button("Save").clicked() {
+                            let c_game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
+                            match patterns::save_pattern_plaintext(
+                                &c_game,
+                                Path::new(&simulation_cfg.pattern_path),
+                            ) {
+                                Ok(()) => simulation_cfg.status_message = "Pattern saved".to_string(),
+                                Err(e) => simulation_cfg.status_message = e.to_string(),
+                            }
+                            drop(c_game);
+                        }
+                    });
+                    if !simulation_cfg.status_message.is_empty() {
+                        ui.label(&simulation_cfg.status_message);
+                    }
                     //Error handling
                     if simulation_cfg.drawing && simulation_cfg.erasing {
                         ui.label("You can't draw and erase at the same time");
@@ -167,61 +381,148 @@ async fn main() {
             continue;
         }
 
-        let mut game = game_mtx.lock().unwrap();
+        let mut game = game_mtx.lock().unwrap_or_else(|e| e.into_inner());
 
-        if is_mouse_button_down(MouseButton::Left) {
-            let fx = mouse_position().0 as f32;
-            let fy = mouse_position().1 as f32;
+        let mouse = Vec2::new(mouse_position().0, mouse_position().1);
+        let camera = make_camera(&simulation_cfg);
 
-            let wx = screen_width() / game.cx as f32;
-            let wy = screen_height() / game.cy as f32;
+        if !simulation_cfg.hovered {
+            //Drag-to-pan with the middle or right mouse button
+            if is_mouse_button_down(MouseButton::Right) || is_mouse_button_down(MouseButton::Middle)
+            {
+                let delta = mouse - prev_mouse;
+                simulation_cfg.translation -= delta / simulation_cfg.zoom;
+            }
 
-            let x = (fx / wx) as i32;
-            let y = (fy / wy) as i32;
+            //Scroll-wheel zoom centered on the cursor
+            let (_, wheel_y) = mouse_wheel();
+            if wheel_y != 0.0 {
+                let world_before = camera.screen_to_world(mouse);
+                simulation_cfg.zoom =
+                    (simulation_cfg.zoom * 1.1f32.powf(wheel_y.signum())).clamp(2.0, 200.0);
+                let world_after = make_camera(&simulation_cfg).screen_to_world(mouse);
+                simulation_cfg.translation += world_before - world_after;
+            }
+        }
+
+        prev_mouse = mouse;
 
-            cell_below_mouse = IVec2::new(x, y);
+        if is_mouse_button_down(MouseButton::Left) {
+            let world = make_camera(&simulation_cfg).screen_to_world(mouse);
+
+            cell_below_mouse = IVec2::new(
+                (world.x / CELL_SIZE).floor() as i32,
+                (world.y / CELL_SIZE).floor() as i32,
+            );
         }
 
         //Resizes the board if the user changes the size of the board
-        if game.cx != simulation_cfg.board_size.x || game.cy != simulation_cfg.board_size.y {
+        if game.cx() != simulation_cfg.board_size.x || game.cy() != simulation_cfg.board_size.y {
             let _ = mem::replace(
                 &mut *game,
-                Board::new(const_ivec2!([
-                    simulation_cfg.board_size.x,
-                    simulation_cfg.board_size.y
-                ])),
+                Board::new(
+                    const_ivec2!([simulation_cfg.board_size.x, simulation_cfg.board_size.y]),
+                    simulation_cfg.board_backend,
+                ),
+            );
+        }
+
+        //Swaps storage backend in place if the user toggled it
+        if game.backend() != simulation_cfg.board_backend {
+            let _ = mem::replace(
+                &mut *game,
+                game.with_backend(simulation_cfg.board_backend, rules.max_state),
             );
         }
 
         //Drawing and erasing
         if is_mouse_button_down(MouseButton::Left) && !simulation_cfg.hovered {
             if simulation_cfg.drawing {
-                game.set_cell_at_position(cell_below_mouse.x, cell_below_mouse.y, 1);
+                game.set_cell_at_position(
+                    cell_below_mouse.x,
+                    cell_below_mouse.y,
+                    rules.max_state,
+                );
             } else if simulation_cfg.erasing {
                 game.set_cell_at_position(cell_below_mouse.x, cell_below_mouse.y, 0);
             }
         }
 
-        //Runs the simulation automatically
-        if simulation_cfg.auto_run || simulation_cfg.should_simulate_next_frame {
+        //Enqueues however many ticks should have elapsed since we last
+        //caught up, so fast rules keep pace and slow ones degrade gracefully.
+        //The queue is capped at MAX_QUEUED_TICKS; any backlog beyond that is
+        //dropped.
+        if simulation_cfg.auto_run {
+            let interval = time::Duration::from_millis((simulation_cfg.wait_time * 10.0) as u64)
+                .max(time::Duration::from_millis(1));
+            let due =
+                (last_tick_at.elapsed().as_secs_f32() / interval.as_secs_f32()).floor() as u32;
+            if due > 0 {
+                simulation_cfg.queued_ticks =
+                    (simulation_cfg.queued_ticks + due).min(MAX_QUEUED_TICKS);
+                last_tick_at += interval * due;
+            }
+        } else {
+            last_tick_at = time::Instant::now();
+        }
+
+        if simulation_cfg.should_simulate_next_frame {
             simulation_cfg.should_simulate_next_frame = false;
+            simulation_cfg.queued_ticks = (simulation_cfg.queued_ticks + 1).min(MAX_QUEUED_TICKS);
+        }
+
+        //Picks up the previous tick's result and, if one is queued, starts the next
+        if handler.is_finished() {
+            let finished = mem::replace(&mut handler, thread::spawn(|| -> TickOutcome { Ok(None) }));
+            match finished.join() {
+                // `None` comes from the idle sentinel handle, not a real tick;
+                // leave the last real measurement on display instead of
+                // flickering it to zero whenever the queue drains.
+                Ok(Ok(Some(duration))) => {
+                    simulation_cfg.last_tick_duration = duration.as_secs_f32()
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => simulation_cfg.status_message = e,
+                Err(_) => simulation_cfg.status_message = "tick thread panicked".to_string(),
+            }
 
-            if handler.is_finished() {
-                let sm_cfg = simulation_cfg.clone();
+            if simulation_cfg.queued_ticks > 0 {
+                simulation_cfg.queued_ticks -= 1;
                 let game_for_thread = game_mtx.clone();
                 let c_rules = rules.clone();
-                handler = thread::spawn(move || {
-                    let time = sm_cfg.wait_time;
-                    thread::sleep(time::Duration::from_millis((time * 10.0) as u64));
-                    let mut c_game = game_for_thread.lock().unwrap();
-                    next_step(&mut c_game, &c_rules);
+                handler = thread::spawn(move || -> TickOutcome {
+                    let mut c_game = game_for_thread
+                        .lock()
+                        .map_err(|_| "board mutex poisoned".to_string())?;
+                    let start = time::Instant::now();
+                    let next = c_game.tick(&c_rules);
+                    *c_game = next;
+                    Ok(Some(start.elapsed()))
                 });
             }
         }
 
+        //Picks up a finished "Run generation" evaluation, if one is in flight
+        let evolve_ready = matches!(&evolve_handler, Some(h) if h.is_finished());
+        if evolve_ready {
+            match evolve_handler.take().unwrap().join() {
+                Ok((next_population, best_genome, best_fitness)) => {
+                    evolve_population = next_population;
+                    simulation_cfg.evolve_generation += 1;
+                    if best_fitness > simulation_cfg.evolve_best_fitness {
+                        simulation_cfg.evolve_best_fitness = best_fitness;
+                        simulation_cfg.evolve_best_rule = best_genome.rule_string();
+                    }
+                }
+                Err(_) => simulation_cfg.status_message = "evolution thread panicked".to_string(),
+            }
+        }
+
         clear_background(simulation_cfg.dead_color);
 
-        draw_board(&game, &simulation_cfg);
+        set_camera(&make_camera(&simulation_cfg));
+        draw_board(&game, &simulation_cfg, &rules);
+        set_default_camera();
 
         egui_macroquad::draw();
 
@@ -229,70 +530,61 @@ async fn main() {
     }
 }
 
-fn next_step(game: &mut Board, rules: &Rules) {
-    let mut new_game_state = Board::new(const_ivec2!([game.cx, game.cy]));
-
-    for y in 0..game.cy {
-        for x in 0..game.cx {
-            let mut neighbors = 0;
+/// Interpolates between `dead` and `alive` by `t` (0 = dead, 1 = fully
+/// alive), so aging Generations cells fade out instead of blinking off.
+fn age_color(dead: Color, alive: Color, t: f32) -> Color {
+    Color::new(
+        dead.r + (alive.r - dead.r) * t,
+        dead.g + (alive.g - dead.g) * t,
+        dead.b + (alive.b - dead.b) * t,
+        dead.a + (alive.a - dead.a) * t,
+    )
+}
 
-            for i in 0..3 {
-                for j in 0..3 {
-                    if i == 1 && j == 1 {
-                        continue;
+fn draw_board(game: &Board, simulation_cfg: &SimulationConfig, rules: &Rules) {
+    match game {
+        // The dense backend still scans the whole grid so that borders are
+        // drawn around dead cells too.
+        Board::Dense(_) => {
+            for x in 0..game.cx() {
+                for y in 0..game.cy() {
+                    let px = x as f32 * CELL_SIZE;
+                    let py = y as f32 * CELL_SIZE;
+                    let state = game.get_cell_at_position(x, y);
+
+                    if state > 0 {
+                        let color = age_color(
+                            simulation_cfg.dead_color,
+                            simulation_cfg.alive_color,
+                            state as f32 / rules.max_state as f32,
+                        );
+                        draw_rectangle(px, py, CELL_SIZE, CELL_SIZE, color);
+                    } else if simulation_cfg.draw_borders {
+                        draw_rectangle_lines(
+                            px,
+                            py,
+                            CELL_SIZE,
+                            CELL_SIZE,
+                            0.05,
+                            simulation_cfg.alive_color,
+                        );
                     }
-                    let px = x + i - 1;
-                    let py = y + j - 1;
-                    neighbors += game.get_cell_at_position(px, py);
                 }
             }
-
-            if game.get_cell_at_position(x, y) > 0 {
-                if rules.survive[neighbors as usize] {
-                    new_game_state.set_cell_at_position(x, y, 1);
-                } else {
-                    new_game_state.lower_cell_lifetime(x, y, 1);
-                }
-            } else if rules.birth[neighbors as usize] {
-                new_game_state.set_cell_at_position(x, y, rules.adding_lifetime.clone());
-            } else {
-                new_game_state.set_cell_at_position(x, y, 0);
-            }
         }
-    }
-
-    game.states = new_game_state.states.clone();
-}
-
-fn draw_board(game: &Board, simulation_cfg: &SimulationConfig) {
-    let wx = screen_width() / game.cx as f32;
-    let wy = screen_height() / game.cy as f32;
-
-    for x in 0..game.cx {
-        for y in 0..game.cy {
-            let px = x as f32 * wx;
-            let py = y as f32 * wy;
-
-            if simulation_cfg.draw_borders {
-                if game.states[(x + y * game.cx) as usize] == 0 {
-                    draw_rectangle_lines(
-                        px as f32,
-                        py as f32,
-                        wx,
-                        wy,
-                        3.0,
-                        simulation_cfg.alive_color,
-                    );
-                } else {
-                    draw_rectangle(px as f32, py as f32, wx, wy, simulation_cfg.alive_color);
-                }
-            } else if game.states[(x + y * game.cx) as usize] == 0 {
-                //draw_rectangle(px as f32, py as f32, wx, wy,  WHITE);
-            } else {
-                draw_rectangle(px as f32, py as f32, wx, wy, simulation_cfg.alive_color);
+        // The sparse backend only visits live cells, keeping draw cost
+        // proportional to the population instead of the board area.
+        Board::Sparse(_) => {
+            for (x, y, state) in game.live_cells() {
+                let px = x as f32 * CELL_SIZE;
+                let py = y as f32 * CELL_SIZE;
+                let color = age_color(
+                    simulation_cfg.dead_color,
+                    simulation_cfg.alive_color,
+                    state as f32 / rules.max_state as f32,
+                );
+                draw_rectangle(px, py, CELL_SIZE, CELL_SIZE, color);
             }
-
-            //Draws the rectangle on the Macroquad window
         }
     }
 }
@@ -0,0 +1,217 @@
+use crate::Board;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A decoded pattern: the bounding box it was authored for and the
+/// coordinates of its live cells, relative to its own top-left corner.
+pub struct PatternData {
+    pub width: i32,
+    pub height: i32,
+    pub cells: Vec<(i32, i32)>,
+}
+
+#[derive(Debug)]
+pub enum PatternError {
+    Io(std::io::Error),
+    Empty,
+    InvalidRleHeader(String),
+    InvalidRleToken(String),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Io(e) => write!(f, "could not read pattern file: {}", e),
+            PatternError::Empty => write!(f, "pattern file is empty"),
+            PatternError::InvalidRleHeader(line) => {
+                write!(f, "invalid RLE header: {}", line)
+            }
+            PatternError::InvalidRleToken(tok) => write!(f, "invalid RLE token: {}", tok),
+        }
+    }
+}
+
+impl From<std::io::Error> for PatternError {
+    fn from(e: std::io::Error) -> Self {
+        PatternError::Io(e)
+    }
+}
+
+/// Loads a pattern from disk, sniffing whether it is RLE (a `#`/`x =` header)
+/// or a plaintext grid, and decodes it accordingly.
+pub fn load_pattern_file(path: &Path) -> Result<PatternData, PatternError> {
+    let text = fs::read_to_string(path)?;
+    let is_rle = text
+        .lines()
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .map(|l| l.trim_start().starts_with('x'))
+        .unwrap_or(false);
+
+    if is_rle {
+        parse_rle(&text)
+    } else {
+        Ok(parse_plaintext(&text))
+    }
+}
+
+/// Parses the MOROS-style plaintext format: `.`, `0` or space mean dead,
+/// any other character (tabs included) means alive.
+fn parse_plaintext(text: &str) -> PatternData {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|l| !l.trim_start().starts_with('!'))
+        .collect();
+
+    let width = lines.iter().map(|l| l.len() as i32).max().unwrap_or(0);
+    let height = lines.len() as i32;
+    let mut cells = Vec::new();
+
+    for (y, line) in lines.iter().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch != '.' && ch != '0' && ch != ' ' {
+                cells.push((x as i32, y as i32));
+            }
+        }
+    }
+
+    PatternData {
+        width,
+        height,
+        cells,
+    }
+}
+
+/// Parses Game-of-Life RLE: a `x = m, y = n` header followed by run tokens
+/// of the form `<count><tag>`, where `b` is dead, `o` is alive, `$` ends a
+/// row and `!` terminates the pattern. A missing count defaults to 1.
+fn parse_rle(text: &str) -> Result<PatternData, PatternError> {
+    let mut width = 0i32;
+    let mut height = 0i32;
+    let mut body = String::new();
+    let mut header_found = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !header_found {
+            let (w, h) = parse_rle_header(trimmed)?;
+            width = w;
+            height = h;
+            header_found = true;
+            continue;
+        }
+        body.push_str(trimmed);
+    }
+
+    if !header_found {
+        return Err(PatternError::Empty);
+    }
+
+    let mut cells = Vec::new();
+    let mut x = 0i32;
+    let mut y = 0i32;
+    let mut count = String::new();
+
+    for ch in body.chars() {
+        if ch.is_ascii_digit() {
+            count.push(ch);
+            continue;
+        }
+
+        let run = if count.is_empty() {
+            1
+        } else {
+            count
+                .parse::<i32>()
+                .map_err(|_| PatternError::InvalidRleToken(count.clone()))?
+        };
+        count.clear();
+
+        match ch {
+            'b' => x += run,
+            'o' => {
+                for i in 0..run {
+                    cells.push((x + i, y));
+                }
+                x += run;
+            }
+            '$' => {
+                y += run;
+                x = 0;
+            }
+            '!' => break,
+            other => return Err(PatternError::InvalidRleToken(other.to_string())),
+        }
+    }
+
+    Ok(PatternData {
+        width,
+        height,
+        cells,
+    })
+}
+
+fn parse_rle_header(line: &str) -> Result<(i32, i32), PatternError> {
+    let mut width = None;
+    let mut height = None;
+
+    for part in line.split(',') {
+        let part = part.trim();
+        if let Some(v) = part.strip_prefix("x") {
+            let v = v.trim_start().trim_start_matches('=').trim();
+            width = v.split_whitespace().next().and_then(|n| n.parse().ok());
+        } else if let Some(v) = part.strip_prefix("y") {
+            let v = v.trim_start().trim_start_matches('=').trim();
+            height = v.split_whitespace().next().and_then(|n| n.parse().ok());
+        }
+    }
+
+    match (width, height) {
+        (Some(w), Some(h)) => Ok((w, h)),
+        _ => Err(PatternError::InvalidRleHeader(line.to_string())),
+    }
+}
+
+/// Stamps a decoded pattern into `board`, centering it on the board (or at
+/// the origin if it doesn't fit), resizing the board first when the pattern
+/// is larger than the current one.
+pub fn stamp_pattern(board: &mut Board, pattern: &PatternData, alive_state: i8) {
+    if pattern.width > board.cx() || pattern.height > board.cy() {
+        *board = Board::new(
+            macroquad::prelude::IVec2::new(
+                pattern.width.max(board.cx()),
+                pattern.height.max(board.cy()),
+            ),
+            board.backend(),
+        );
+    } else {
+        board.clear();
+    }
+
+    let ox = (board.cx() - pattern.width) / 2;
+    let oy = (board.cy() - pattern.height) / 2;
+
+    for (x, y) in &pattern.cells {
+        board.set_cell_at_position(ox + x, oy + y, alive_state);
+    }
+}
+
+/// Writes `board` out as a plaintext grid (`.` for dead, `O` for alive).
+pub fn save_pattern_plaintext(board: &Board, path: &Path) -> Result<(), PatternError> {
+    let mut text = String::with_capacity(((board.cx() + 1) * board.cy()) as usize);
+    for y in 0..board.cy() {
+        for x in 0..board.cx() {
+            text.push(if board.get_cell_at_position(x, y) > 0 {
+                'O'
+            } else {
+                '.'
+            });
+        }
+        text.push('\n');
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
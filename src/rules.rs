@@ -0,0 +1,98 @@
+use std::fmt;
+
+/// A named rule preset in Golly's B/S notation, e.g. Conway's Life is `B3/S23`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RulePreset {
+    ConwaysLife,
+    HighLife,
+    Seeds,
+    DayAndNight,
+    Replicator,
+}
+
+impl RulePreset {
+    pub const ALL: &'static [RulePreset] = &[
+        RulePreset::ConwaysLife,
+        RulePreset::HighLife,
+        RulePreset::Seeds,
+        RulePreset::DayAndNight,
+        RulePreset::Replicator,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RulePreset::ConwaysLife => "Conway's Life",
+            RulePreset::HighLife => "HighLife",
+            RulePreset::Seeds => "Seeds",
+            RulePreset::DayAndNight => "Day & Night",
+            RulePreset::Replicator => "Replicator",
+        }
+    }
+
+    pub fn rule_string(&self) -> &'static str {
+        match self {
+            RulePreset::ConwaysLife => "B3/S23",
+            RulePreset::HighLife => "B36/S23",
+            RulePreset::Seeds => "B2/S",
+            RulePreset::DayAndNight => "B3678/S34678",
+            RulePreset::Replicator => "B1357/S1357",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RuleParseError(String);
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rule string '{}', expected Bx/Sy", self.0)
+    }
+}
+
+/// Formats birth/survive tables back into Golly notation, e.g. `B3/S23`.
+pub fn format_rule_string(birth: &[bool], survive: &[bool]) -> String {
+    let digits = |table: &[bool]| -> String {
+        table
+            .iter()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .map(|(neighbors, _)| neighbors.to_string())
+            .collect()
+    };
+    format!("B{}/S{}", digits(birth), digits(survive))
+}
+
+/// Parses a Golly-style rule string like `B3/S23` into birth/survive tables
+/// indexed by neighbor count (0-8). Rejects malformed input and digits
+/// outside the 0-8 neighbor range.
+pub fn parse_rule_string(input: &str) -> Result<(Vec<bool>, Vec<bool>), RuleParseError> {
+    let invalid = || RuleParseError(input.to_string());
+
+    let mut parts = input.trim().splitn(2, '/');
+    let b_part = parts.next().ok_or_else(invalid)?;
+    let s_part = parts.next().ok_or_else(invalid)?;
+
+    let b_digits = b_part
+        .strip_prefix('B')
+        .or_else(|| b_part.strip_prefix('b'))
+        .ok_or_else(invalid)?;
+    let s_digits = s_part
+        .strip_prefix('S')
+        .or_else(|| s_part.strip_prefix('s'))
+        .ok_or_else(invalid)?;
+
+    let mut birth = vec![false; 9];
+    let mut survive = vec![false; 9];
+
+    for (digits, table) in [(b_digits, &mut birth), (s_digits, &mut survive)] {
+        for ch in digits.chars() {
+            let neighbors = ch.to_digit(10).ok_or_else(invalid)? as usize;
+            if neighbors > 8 {
+                return Err(invalid());
+            }
+            table[neighbors] = true;
+        }
+    }
+
+    Ok((birth, survive))
+}